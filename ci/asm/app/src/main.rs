@@ -1,7 +1,7 @@
 #![no_main]
 #![no_std]
 
-use rt::entry;
+use rt::{entry, ExceptionFrame};
 
 entry!(main);
 
@@ -11,6 +11,6 @@ fn main() -> ! {
 
 #[allow(non_snake_case)]
 #[no_mangle]
-pub fn HardFault(_ef: *const u32) -> ! {
+pub fn HardFault(_ef: &ExceptionFrame) -> ! {
     loop {}
 }
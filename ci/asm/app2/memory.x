@@ -0,0 +1 @@
+../../main/app4/memory.x
\ No newline at end of file
@@ -2,5 +2,12 @@
   .global HardFaultTrampoline
   .thumb_func
 HardFaultTrampoline:
+  mov r0, lr
+  movs r1, #4
+  tst r0, r1
+  bne 0f
   mrs r0, MSP
   b HardFault
+0:
+  mrs r0, PSP
+  b HardFault
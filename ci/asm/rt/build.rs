@@ -2,6 +2,8 @@ use std::{env, error::Error, fs::File, io::Write, path::PathBuf};
 
 use cc::Build;
 
+mod memory_x;
+
 fn main() -> Result<(), Box<Error>> {
     // このクレートのビルドディレクトリです
     let out_dir = PathBuf::from(env::var_os("OUT_DIR").unwrap());
@@ -11,9 +13,14 @@ fn main() -> Result<(), Box<Error>> {
 
     // `link.x`をビルドディレクトリに置きます
     File::create(out_dir.join("link.x"))?.write_all(include_bytes!("link.x"))?;
+    println!("cargo:rerun-if-changed=link.x");
+
+    // `link.x`が`INCLUDE`する`memory.x`を用意します
+    memory_x::provide(&out_dir)?;
 
     // `asm.s`ファイルをアセンブルします
     Build::new().file("asm.s").compile("asm"); // <- 追加!
+    println!("cargo:rerun-if-changed=asm.s");
 
     Ok(())
 }
@@ -1,10 +1,5 @@
-/* Memory layout of the LM3S6965 microcontroller */
-/* 1K = 1 KiBi = 1024 bytes */
-MEMORY
-{
-  FLASH : ORIGIN = 0x00000000, LENGTH = 256K
-  RAM : ORIGIN = 0x20000000, LENGTH = 64K
-}
+/* The memory layout of the device (`MEMORY`) is provided by the application in `memory.x` */
+INCLUDE memory.x
 
 /* The entry point is the reset handler */
 ENTRY(Reset);
@@ -35,19 +30,24 @@ SECTIONS
   .rodata :
   {
     *(.rodata .rodata.*);
+
+    /* Makes the initial values of `.data` word aligned */
+    . = ALIGN(4);
   } > FLASH
 
-  .bss :
+  .bss : ALIGN(4)
   {
     _sbss = .;
     *(.bss .bss.*);
+    . = ALIGN(4);
     _ebss = .;
   } > RAM
 
-  .data : AT(ADDR(.rodata) + SIZEOF(.rodata))
+  .data : AT(ADDR(.rodata) + SIZEOF(.rodata)) ALIGN(4)
   {
     _sdata = .;
     *(.data .data.*);
+    . = ALIGN(4);
     _edata = .;
   } > RAM
 
@@ -67,3 +67,19 @@ PROVIDE(UsageFault = DefaultExceptionHandler);
 PROVIDE(SVCall = DefaultExceptionHandler);
 PROVIDE(PendSV = DefaultExceptionHandler);
 PROVIDE(SysTick = DefaultExceptionHandler);
+
+/* Sanity checks of the vector table and of the RAM initialization symbols */
+ASSERT(RESET_VECTOR == ADDR(.vector_table) + 4,
+       "ERROR(rt): the reset vector must be the second entry of the vector table");
+ASSERT(EXCEPTIONS == ADDR(.vector_table) + 8,
+       "ERROR(rt): the exception vectors must follow the reset vector");
+ASSERT(SIZEOF(.vector_table) == 0x40,
+       "ERROR(rt): the vector table must contain 16 entries (initial SP, reset and 14 exceptions)");
+ASSERT(_sbss % 4 == 0 && _ebss % 4 == 0,
+       "ERROR(rt): the start and end of .bss must be 4-byte aligned");
+ASSERT(_sdata % 4 == 0 && _edata % 4 == 0,
+       "ERROR(rt): the start and end of .data must be 4-byte aligned");
+ASSERT(_sidata % 4 == 0,
+       "ERROR(rt): the load address of .data (_sidata) must be 4-byte aligned");
+ASSERT(_sidata >= ORIGIN(FLASH) && _sidata + (_edata - _sdata) <= ORIGIN(FLASH) + LENGTH(FLASH),
+       "ERROR(rt): the initial values of .data (_sidata) must be located in FLASH");
@@ -94,3 +94,16 @@ pub static EXCEPTIONS: [Vector; 14] = [
 pub extern "C" fn DefaultExceptionHandler() {
     loop {}
 }
+
+// 例外発生時にハードウェアがスタックにプッシュするレジスタ
+#[repr(C)]
+pub struct ExceptionFrame {
+    pub r0: u32,
+    pub r1: u32,
+    pub r2: u32,
+    pub r3: u32,
+    pub r12: u32,
+    pub lr: u32,
+    pub pc: u32,
+    pub xpsr: u32,
+}
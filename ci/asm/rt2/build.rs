@@ -3,9 +3,12 @@ use std::{
     error::Error,
     fs::{self, File},
     io::Write,
-    path::PathBuf,
+    path::{Path, PathBuf},
+    process::{Command, Stdio},
 };
 
+mod memory_x;
+
 fn main() -> Result<(), Box<Error>> {
     // このクレートのビルドディレクトリです
     let out_dir = PathBuf::from(env::var_os("OUT_DIR").unwrap());
@@ -15,10 +18,137 @@ fn main() -> Result<(), Box<Error>> {
 
     // `link.x`をビルドディレクトリに置きます
     File::create(out_dir.join("link.x"))?.write_all(include_bytes!("link.x"))?;
+    println!("cargo:rerun-if-changed=link.x");
+
+    // `link.x`が`INCLUDE`する`memory.x`を用意します
+    memory_x::provide(&out_dir)?;
+
+    // アセンブラがあれば`asm.s`から`librt.a`を作り、なければあらかじめアセンブルした
+    // `librt.a`を使います
+    let target = env::var("TARGET")?;
+    println!("cargo:rerun-if-env-changed=RT_USE_PREBUILT");
+    if env::var_os("RT_USE_PREBUILT").is_some() || !assemble(&target, &out_dir)? {
+        check_prebuilt()?;
+        fs::copy("librt.a", out_dir.join("librt.a"))?; // <- 追加!
+    }
 
     // `librt.a`にリンクします
-    fs::copy("librt.a", out_dir.join("librt.a"))?; // <- 追加!
     println!("cargo:rustc-link-lib=static=rt"); // <- 追加!
+    println!("cargo:rerun-if-changed=asm.s");
+    println!("cargo:rerun-if-changed=librt.a");
+    println!("cargo:rerun-if-changed=librt.a.hash");
 
     Ok(())
 }
+
+// `asm.s`をアセンブルし、`out_dir`に`librt.a`を作ります。アセンブラ（`arm-none-eabi-as`か
+// `clang`）と対応するアーカイバが見つからなければ`false`を返します
+fn assemble(target: &str, out_dir: &Path) -> Result<bool, Box<Error>> {
+    let gnu = available("arm-none-eabi-as") && available("arm-none-eabi-ar");
+    let llvm = available("clang") && available("llvm-ar");
+
+    let (mut assembler, archiver) = if gnu {
+        let mut assembler = Command::new("arm-none-eabi-as");
+        assembler
+            .arg(format!("-march={}", march(target)?))
+            .arg("-mthumb");
+        (assembler, "arm-none-eabi-ar")
+    } else if llvm {
+        let mut assembler = Command::new("clang");
+        assembler.arg(format!("--target={}", target)).arg("-c");
+        (assembler, "llvm-ar")
+    } else {
+        return Ok(false);
+    };
+
+    if target.ends_with("-eabihf") {
+        assembler.arg("-mfloat-abi=hard");
+    }
+
+    let obj = out_dir.join("asm.o");
+    let lib = out_dir.join("librt.a");
+    run(assembler.arg("asm.s").arg("-o").arg(&obj))?;
+
+    // 前回のビルドのメンバが残らないよう、アーカイブは作り直します
+    if lib.exists() {
+        fs::remove_file(&lib)?;
+    }
+    run(Command::new(archiver).arg("crs").arg(&lib).arg(&obj))?;
+
+    Ok(true)
+}
+
+// `arm-none-eabi-as`の`-march`に渡すアーキテクチャ名です
+fn march(target: &str) -> Result<&'static str, Box<Error>> {
+    Ok(match target.split('-').next() {
+        Some("thumbv6m") => "armv6s-m",
+        Some("thumbv7m") => "armv7-m",
+        Some("thumbv7em") => "armv7e-m",
+        Some("thumbv8m.base") => "armv8-m.base",
+        Some("thumbv8m.main") => "armv8-m.main",
+        _ => return Err(format!("unsupported target: {}", target).into()),
+    })
+}
+
+// `program`が実行できるかを調べます
+fn available(program: &str) -> bool {
+    Command::new(program)
+        .arg("--version")
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()
+        .map(|status| status.success())
+        .unwrap_or(false)
+}
+
+fn run(command: &mut Command) -> Result<(), Box<Error>> {
+    let status = command.status()?;
+    if !status.success() {
+        return Err(format!("{:?} failed: {}", command, status).into());
+    }
+
+    Ok(())
+}
+
+// あらかじめアセンブルした`librt.a`が、現在の`asm.s`から作られたものかを確かめます。
+// `librt.a.hash`には、`librt.a`を作ったときの`asm.s`と、作られた`librt.a`のハッシュが
+// 記録されています。`librt.a`の中身が`asm.s`をアセンブルした結果と一致することは、CIが
+// `asm.s`をアセンブルし直して確かめます
+fn check_prebuilt() -> Result<(), Box<Error>> {
+    let recorded =
+        fs::read_to_string("librt.a.hash").map_err(|e| format!("librt.a.hash: {}", e))?;
+
+    // チェックアウトによって変わる改行コード（CR）は無視します
+    let mut asm = fs::read("asm.s")?;
+    asm.retain(|byte| *byte != b'\r');
+
+    for (file, bytes) in &[("asm.s", asm), ("librt.a", fs::read("librt.a")?)] {
+        let hash = format!("{:016x}", fnv1a(bytes));
+        let expected = recorded.lines().find_map(|line| {
+            let mut fields = line.split_whitespace();
+            match (fields.next(), fields.next()) {
+                (Some(hash), Some(name)) if name == *file => Some(hash),
+                _ => None,
+            }
+        });
+
+        if expected != Some(&*hash) {
+            return Err(format!(
+                "librt.a is out of date: `{}` (hash {}) doesn't match the hash recorded in \
+                 `librt.a.hash`. Reassemble `librt.a` from `asm.s` with `arm-none-eabi-as` and \
+                 record the hashes of both `asm.s` and the new `librt.a` in `librt.a.hash`",
+                file, hash
+            )
+            .into());
+        }
+    }
+
+    Ok(())
+}
+
+// FNV-1a（64ビット）
+fn fnv1a(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf2_9ce4_8422_2325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    })
+}
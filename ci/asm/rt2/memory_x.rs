@@ -0,0 +1 @@
+../../main/rt2/memory_x.rs
\ No newline at end of file
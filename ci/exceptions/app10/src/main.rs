@@ -0,0 +1,33 @@
+#![no_main]
+#![no_std]
+
+use core::ptr;
+
+use cortex_m_semihosting::debug;
+
+use rt::{entry, Exception};
+
+entry!(main);
+
+static mut TICKS: u32 = 0;
+
+extern "C" fn on_tick() {
+    unsafe { ptr::write_volatile(ptr::addr_of_mut!(TICKS), 1) }
+}
+
+fn main() -> ! {
+    // ベクタテーブルをRAMにコピーし、SysTickのハンドラを実行時に差し替えます
+    rt::relocate_vector_table();
+    rt::set_handler(Exception::SysTick, on_tick);
+
+    // ICSRのPENDSTSETビットに書き込んで、SysTick例外を発生させます
+    unsafe { ptr::write_volatile(0xE000_ED04 as *mut u32, 1 << 26) }
+
+    if unsafe { ptr::read_volatile(ptr::addr_of!(TICKS)) } == 1 {
+        debug::exit(debug::EXIT_SUCCESS);
+    } else {
+        debug::exit(debug::EXIT_FAILURE);
+    }
+
+    loop {}
+}
@@ -0,0 +1,6 @@
+/* アプリケーションはブートローダの後ろに置きます */
+MEMORY
+{
+  FLASH : ORIGIN = 0x00004000, LENGTH = 240K
+  RAM : ORIGIN = 0x20000000, LENGTH = 64K
+}
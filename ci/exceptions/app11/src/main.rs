@@ -0,0 +1,15 @@
+#![no_main]
+#![no_std]
+
+use cortex_m_semihosting::debug;
+
+use rt::entry;
+
+entry!(main);
+
+// ブートローダから起動されるアプリケーションです
+fn main() -> ! {
+    debug::exit(debug::EXIT_SUCCESS);
+
+    loop {}
+}
@@ -0,0 +1,28 @@
+#![no_main]
+#![no_std]
+
+use cortex_m_semihosting::debug;
+
+use rt::{entry, ramfunc};
+
+entry!(main);
+
+ramfunc! {
+    // この関数はRAMから実行されます
+    fn add(x: u32, y: u32) -> u32 {
+        x.wrapping_add(y)
+    }
+}
+
+fn main() -> ! {
+    // `add`のアドレスがRAM（0x2000_0000から）にあることを確認します
+    let in_ram = add as *const () as usize & 0xFF00_0000 == 0x2000_0000;
+
+    if in_ram && add(1, 2) == 3 {
+        debug::exit(debug::EXIT_SUCCESS);
+    } else {
+        debug::exit(debug::EXIT_FAILURE);
+    }
+
+    loop {}
+}
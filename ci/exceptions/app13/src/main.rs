@@ -0,0 +1,32 @@
+#![no_main]
+#![no_std]
+
+use core::ptr;
+
+use cortex_m_semihosting::debug;
+
+use rt::entry;
+
+entry!(main);
+
+static mut CONSTRUCTED: u32 = 0;
+
+extern "C" fn constructor() {
+    unsafe { ptr::write_volatile(ptr::addr_of_mut!(CONSTRUCTED), 1) }
+}
+
+// Cのライブラリが`.init_array`に置く静的コンストラクタの代わりです
+#[link_section = ".init_array"]
+#[used]
+static CONSTRUCTOR: extern "C" fn() = constructor;
+
+fn main() -> ! {
+    // `main`が呼ばれる前に、コンストラクタが実行されています
+    if unsafe { ptr::read_volatile(ptr::addr_of!(CONSTRUCTED)) } == 1 {
+        debug::exit(debug::EXIT_SUCCESS);
+    } else {
+        debug::exit(debug::EXIT_FAILURE);
+    }
+
+    loop {}
+}
@@ -0,0 +1,29 @@
+#![no_main]
+#![no_std]
+
+use cortex_m_semihosting::{debug, hprintln};
+
+use rt::{entry, BootInfo, ResetCause};
+
+entry!(main);
+
+fn main() -> ! {
+    // `boot-info`フィーチャでは、`MainTrampoline`がリセット要因を読み出し、`.uninit`に
+    // 記録しています
+    let info = rt::boot_info();
+
+    hprintln!("{:?}", info).ok();
+
+    match info {
+        // 1回目の起動。ソフトウェアリセットで再起動します
+        Some(BootInfo { boot_count: 1, .. }) => rt::system_reset(),
+        // 2回目の起動。ソフトウェアリセットとして数えられているはずです
+        Some(BootInfo {
+            boot_count: 2,
+            reset_cause: ResetCause::Software,
+        }) => debug::exit(debug::EXIT_SUCCESS),
+        _ => debug::exit(debug::EXIT_FAILURE),
+    }
+
+    loop {}
+}
@@ -0,0 +1,53 @@
+#![no_main]
+#![no_std]
+
+use core::ptr;
+
+use cortex_m_semihosting::debug;
+
+use rt::{entry, global_watchdog, pre_init, uninit, Watchdog};
+
+entry!(main);
+
+// `Reset`が初期化しない領域に、餌を与えた回数を数えます
+uninit! {
+    static mut FEEDS: u32;
+}
+
+fn feeds() -> *mut u32 {
+    ptr::addr_of_mut!(FEEDS).cast::<u32>()
+}
+
+pre_init!(reset_feeds);
+
+unsafe fn reset_feeds() {
+    ptr::write_volatile(feeds(), 0);
+}
+
+struct CountingWatchdog;
+
+impl Watchdog for CountingWatchdog {
+    fn feed(&self) {
+        // 実際のウォッチドッグでは、ここでカウンタを再読み込みします
+        unsafe { ptr::write_volatile(feeds(), ptr::read_volatile(feeds()) + 1) }
+    }
+}
+
+global_watchdog!(CountingWatchdog);
+
+// 8KiBの`.bss`。ゼロクリアの間に`Reset`が何度か餌を与えます
+static mut BUFFER: [u8; 8 * 1024] = [0; 8 * 1024];
+
+fn main() -> ! {
+    let buffer = unsafe { ptr::read_volatile(ptr::addr_of!(BUFFER).cast::<u8>()) };
+
+    if buffer == 0 && unsafe { ptr::read_volatile(feeds()) } >= 8 {
+        debug::exit(debug::EXIT_SUCCESS);
+    } else {
+        debug::exit(debug::EXIT_FAILURE);
+    }
+
+    loop {
+        rt::feed_watchdog();
+    }
+}
@@ -0,0 +1,11 @@
+#![no_main]
+#![no_std]
+
+use rt::entry;
+
+entry!(main);
+
+fn main() -> ! {
+    // `panic-semihosting`フィーチャでは、メッセージが表示され、QEMUが失敗として終了します
+    panic!("Oops")
+}
@@ -0,0 +1,28 @@
+#![no_main]
+#![no_std]
+
+use cortex_m_semihosting::hprintln;
+
+use rt::entry;
+
+entry!(main);
+
+#[derive(Debug)]
+enum Error {
+    Overflow,
+}
+
+fn sum(xs: &[u32]) -> Result<u32, Error> {
+    xs.iter()
+        .try_fold(0u32, |acc, x| acc.checked_add(*x))
+        .ok_or(Error::Overflow)
+}
+
+// `Ok`を返すとQEMUは成功として終了し、`Err`を返すとエラーが表示され失敗として終了します
+fn main() -> Result<(), Error> {
+    let x = sum(&[1, 2, 3])?;
+
+    hprintln!("sum: {}", x).ok();
+
+    Ok(())
+}
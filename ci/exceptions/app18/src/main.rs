@@ -0,0 +1,61 @@
+#![feature(custom_test_frameworks)]
+#![no_main]
+#![no_std]
+#![reexport_test_harness_main = "test_main"]
+#![test_runner(rt::test_runner)]
+
+use cortex_m_semihosting::debug;
+
+use rt::entry;
+
+entry!(main);
+
+fn main() -> ! {
+    // `cargo test`でビルドした場合は、`#[test_case]`のテストを実行します
+    #[cfg(test)]
+    test_main();
+
+    debug::exit(debug::EXIT_SUCCESS);
+
+    loop {}
+}
+
+#[test_case]
+fn parse() {
+    assert_eq!(u32::from_str_radix("2a", 16), Ok(42));
+}
+
+// このテストは失敗しますが、テストランナーは次のテストを実行し続けます
+#[test_case]
+fn overflow() {
+    let x: u8 = 255;
+    let _ = x + core::hint::black_box(1);
+}
+
+// リセットをまたいで値が保持されることを確かめるための目印です
+#[cfg(test)]
+const SENTINEL: u32 = 0x5E47_1AE1;
+
+#[cfg(test)]
+rt::uninit! {
+    static mut MARK: u32;
+}
+
+// 目印を書き込んでからシステムリセットを要求します。ランナーはリセット後、次のテストから
+// 再開します
+#[test_case]
+fn reset() {
+    unsafe { core::ptr::write_volatile(core::ptr::addr_of_mut!(MARK).cast::<u32>(), SENTINEL) }
+    cortex_m_semihosting::hprintln!("reset").ok();
+
+    rt::system_reset()
+}
+
+// `reset`の直後に実行され、リセット前に書き込んだ目印が残っていることを確かめます
+#[test_case]
+fn uninit_survives_reset() {
+    let mark = unsafe { core::ptr::read_volatile(core::ptr::addr_of!(MARK).cast::<u32>()) };
+    unsafe { core::ptr::write_volatile(core::ptr::addr_of_mut!(MARK).cast::<u32>(), 0) }
+
+    assert_eq!(mark, SENTINEL);
+}
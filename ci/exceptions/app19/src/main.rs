@@ -0,0 +1,15 @@
+#![no_main]
+#![no_std]
+
+use rt::{entry, exception};
+
+entry!(main);
+
+fn main() -> ! {
+    loop {}
+}
+
+// ARMv6-MにはMemManage例外がないため、このクレートはコンパイルエラーになります
+exception!(MemManage, mem_manage);
+
+fn mem_manage() {}
@@ -0,0 +1,28 @@
+#![no_main]
+#![no_std]
+
+use cortex_m_semihosting::hprintln;
+
+use rt::entry;
+
+entry!(main);
+
+#[derive(Debug)]
+enum Error {
+    Overflow,
+}
+
+fn sum(xs: &[u32]) -> Result<u32, Error> {
+    xs.iter()
+        .try_fold(0u32, |acc, x| acc.checked_add(*x))
+        .ok_or(Error::Overflow)
+}
+
+// 和がオーバーフローするため`main`は`Err`を返し、QEMUはエラーを表示して失敗として終了します
+fn main() -> Result<(), Error> {
+    let x = sum(&[u32::MAX, 1])?;
+
+    hprintln!("sum: {}", x).ok();
+
+    Ok(())
+}
@@ -0,0 +1,23 @@
+#![no_main]
+#![no_std]
+
+use rt::{entry, exception};
+
+entry!(main);
+
+fn main() -> ! {
+    loop {}
+}
+
+exception!(HardFault, hard_fault);
+
+fn hard_fault() -> ! {
+    // ここで何か面白いことをやります
+    loop {}
+}
+
+exception!(SysTick, sys_tick);
+
+fn sys_tick() {
+    // ここで何か面白いことをやります
+}
@@ -0,0 +1,14 @@
+#![no_main]
+#![no_std]
+
+use rt::{entry, exception};
+
+entry!(main);
+
+fn main() -> ! {
+    loop {}
+}
+
+exception!(SysTick, sys_tick);
+
+fn sys_tick() {}
@@ -0,0 +1,31 @@
+#![no_main]
+#![no_std]
+
+use core::ptr;
+
+use cortex_m_semihosting::debug;
+
+use rt::{entry, exception};
+
+entry!(main);
+
+fn main() -> ! {
+    // SCB->ICSRのPENDSVSETビットに書き込んで、PendSV例外を発生させます
+    unsafe { ptr::write_volatile(0xE000_ED04 as *mut u32, 1 << 28) }
+
+    // ハンドラが呼ばれなかった場合
+    debug::exit(debug::EXIT_FAILURE);
+
+    loop {}
+}
+
+exception!(DefaultHandler, default_handler);
+
+fn default_handler(irqn: i16) {
+    // PendSVの場合、`irqn`は-2になります
+    if irqn == -2 {
+        debug::exit(debug::EXIT_SUCCESS);
+    } else {
+        debug::exit(debug::EXIT_FAILURE);
+    }
+}
@@ -0,0 +1,29 @@
+#![no_main]
+#![no_std]
+
+use core::ptr;
+
+use cortex_m_semihosting::{debug, hprintln};
+
+use rt::entry;
+
+entry!(main);
+
+fn main() -> ! {
+    // スタックに256バイトのバッファを置きます
+    let mut buffer = [0u8; 256];
+    for byte in buffer.iter_mut() {
+        unsafe { ptr::write_volatile(byte, 0xff) }
+    }
+
+    let used = rt::stack_high_water_mark();
+    hprintln!("stack high water mark: {} bytes", used).ok();
+
+    if used >= buffer.len() {
+        debug::exit(debug::EXIT_SUCCESS);
+    } else {
+        debug::exit(debug::EXIT_FAILURE);
+    }
+
+    loop {}
+}
@@ -0,0 +1,37 @@
+#![no_main]
+#![no_std]
+
+use core::ptr;
+
+use cortex_m_semihosting::debug;
+
+use rt::entry;
+
+entry!(main);
+
+static mut ZERO: u32 = 0;
+static mut ONE: u32 = 1;
+
+fn main() -> ! {
+    extern "C" {
+        static _stack_start: u32;
+    }
+
+    // `.bss`と`.data`はスタックの上に置かれ、`Reset`によって初期化されています
+    let ok = unsafe {
+        let stack_start = ptr::addr_of!(_stack_start) as usize;
+
+        ptr::addr_of!(ZERO) as usize >= stack_start
+            && ptr::addr_of!(ONE) as usize >= stack_start
+            && ptr::read_volatile(ptr::addr_of!(ZERO)) == 0
+            && ptr::read_volatile(ptr::addr_of!(ONE)) == 1
+    };
+
+    if ok {
+        debug::exit(debug::EXIT_SUCCESS);
+    } else {
+        debug::exit(debug::EXIT_FAILURE);
+    }
+
+    loop {}
+}
@@ -0,0 +1,52 @@
+#![no_main]
+#![no_std]
+
+use core::ptr;
+
+use cortex_m_semihosting::debug;
+
+use rt::{entry, uninit};
+
+entry!(main);
+
+// 電源投入直後の不定値と、初期化済みの値を区別するためのマジックナンバー
+const MAGIC: u32 = 0xDEAD_BEEF;
+
+#[repr(C)]
+struct BootInfo {
+    magic: u32,
+    resets: u32,
+}
+
+uninit! {
+    static mut BOOT_INFO: BootInfo;
+}
+
+fn main() -> ! {
+    let info = ptr::addr_of_mut!(BOOT_INFO).cast::<BootInfo>();
+
+    unsafe {
+        if ptr::read_volatile(ptr::addr_of!((*info).magic)) != MAGIC {
+            // 電源投入直後（コールドブート）
+            ptr::write_volatile(ptr::addr_of_mut!((*info).magic), MAGIC);
+            ptr::write_volatile(ptr::addr_of_mut!((*info).resets), 0);
+        }
+
+        let resets = ptr::read_volatile(ptr::addr_of!((*info).resets));
+        if resets == 0 {
+            ptr::write_volatile(ptr::addr_of_mut!((*info).resets), resets + 1);
+
+            // 戻ってきません。`main`は2回目の起動で再び呼ばれます
+            rt::system_reset();
+        }
+
+        // 2回目の起動です。リセットの後も`.uninit`の値が残っていれば、`resets`は1です
+        if resets == 1 {
+            debug::exit(debug::EXIT_SUCCESS);
+        } else {
+            debug::exit(debug::EXIT_FAILURE);
+        }
+    }
+
+    loop {}
+}
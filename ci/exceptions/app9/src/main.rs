@@ -0,0 +1,29 @@
+#![no_main]
+#![no_std]
+
+extern crate alloc;
+
+use alloc::{boxed::Box, vec::Vec};
+
+use cortex_m_semihosting::debug;
+
+use rt::entry;
+
+entry!(main);
+
+fn main() -> ! {
+    // `alloc`フィーチャが有効なら、ヒープ領域からメモリを確保できます
+    let x = Box::new(42);
+    let mut xs = Vec::new();
+    for i in 0..100 {
+        xs.push(i);
+    }
+
+    if *x == 42 && xs.iter().sum::<i32>() == 4950 {
+        debug::exit(debug::EXIT_SUCCESS);
+    } else {
+        debug::exit(debug::EXIT_FAILURE);
+    }
+
+    loop {}
+}
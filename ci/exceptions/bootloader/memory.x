@@ -0,0 +1,6 @@
+/* ブートローダは先頭の16KiBを使います */
+MEMORY
+{
+  FLASH : ORIGIN = 0x00000000, LENGTH = 16K
+  RAM : ORIGIN = 0x20000000, LENGTH = 64K
+}
@@ -0,0 +1,15 @@
+#![no_main]
+#![no_std]
+
+use rt::entry;
+
+entry!(main);
+
+// アプリケーションのベクタテーブルのアドレス（`app11/memory.x`のFLASHの開始位置）
+const APP_VECTOR_TABLE: u32 = 0x0000_4000;
+
+fn main() -> ! {
+    // ここでアプリケーションのイメージを検証したり、更新したりします
+
+    unsafe { rt::jump_to(APP_VECTOR_TABLE) }
+}
@@ -0,0 +1,118 @@
+  .section .text.Reset
+  .global Reset
+  .thumb_func
+Reset:
+  ldr r0, =__rt_has_fpu
+  cmp r0, #0
+  beq 0f
+
+  ldr r0, =0xE000ED88
+  ldr r1, [r0]
+  ldr r2, =0x00F00000
+  orrs r1, r2
+  str r1, [r0]
+  dsb
+  isb
+
+0:
+  bl __pre_init
+
+  ldr r4, =_sbss
+  ldr r5, =_ebss
+  movs r6, #0
+1:
+  cmp r4, r5
+  beq 2f
+  stmia r4!, {r6}
+  lsls r0, r4, #22
+  bne 1b
+  bl __rt_feed_watchdog
+  b 1b
+
+2:
+  ldr r4, =_sdata
+  ldr r5, =_edata
+  ldr r6, =_sidata
+3:
+  cmp r4, r5
+  beq 4f
+  ldmia r6!, {r7}
+  stmia r4!, {r7}
+  lsls r0, r4, #22
+  bne 3b
+  bl __rt_feed_watchdog
+  b 3b
+
+4:
+  ldr r0, =_sramfunc
+  ldr r1, =_eramfunc
+  ldr r2, =_siramfunc
+5:
+  cmp r0, r1
+  beq 6f
+  ldmia r2!, {r3}
+  stmia r0!, {r3}
+  b 5b
+
+6:
+  ldr r0, =__rt_paint_stack
+  cmp r0, #0
+  beq 8f
+
+  ldr r0, =_stack_end
+  mov r1, sp
+  ldr r2, =0xCCCCCCCC
+7:
+  cmp r0, r1
+  bhs 8f
+  stmia r0!, {r2}
+  b 7b
+
+8:
+  bl MainTrampoline
+
+  .section .text.DefaultHandler_
+  .global DefaultHandler_
+  .thumb_func
+DefaultHandler_:
+  bkpt 0xff
+  b DefaultHandler_
+
+  .section .text.__rt_interrupt_disable
+  .global __rt_interrupt_disable
+  .thumb_func
+__rt_interrupt_disable:
+  mrs r0, PRIMASK
+  cpsid i
+  bx lr
+
+  .section .text.__rt_interrupt_restore
+  .global __rt_interrupt_restore
+  .thumb_func
+__rt_interrupt_restore:
+  msr PRIMASK, r0
+  bx lr
+
+  .section .text.__rt_barrier
+  .global __rt_barrier
+  .thumb_func
+__rt_barrier:
+  dsb
+  isb
+  bx lr
+
+  .section .text.__rt_jump_to
+  .global __rt_jump_to
+  .thumb_func
+__rt_jump_to:
+  ldr r1, [r0]
+  ldr r2, [r0, #4]
+  msr MSP, r1
+  bx r2
+
+  .section .text.__rt_panic_bkpt
+  .global __rt_panic_bkpt
+  .thumb_func
+__rt_panic_bkpt:
+  bkpt 0xfe
+  b __rt_panic_bkpt
@@ -1 +0,0 @@
-../../main/rt/build.rs
\ No newline at end of file
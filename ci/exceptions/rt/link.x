@@ -1,29 +1,28 @@
-/* LM3S6965マイクロコントローラのメモリレイアウト */
-/* 1K = 1 KiBi = 1024バイト */
-MEMORY
-{
-  FLASH : ORIGIN = 0x00000000, LENGTH = 256K
-  RAM : ORIGIN = 0x20000000, LENGTH = 64K
-}
+/* デバイスのメモリレイアウト（`MEMORY`）はアプリケーションが`memory.x`で提供します */
+INCLUDE memory.x
 
 /* エントリポイントはリセットハンドラです */
 ENTRY(Reset);
 
 EXTERN(RESET_VECTOR);
 EXTERN(EXCEPTIONS); /* <- 追加 */
+EXTERN(__INTERRUPTS);
 
 SECTIONS
 {
   .vector_table ORIGIN(FLASH) :
   {
     /* 1つ目のエントリ。スタックポインタの初期値 */
-    LONG(ORIGIN(RAM) + LENGTH(RAM));
+    LONG(_stack_start);
 
     /* 2つ目のエントリ。リセットベクタ */
     KEEP(*(.vector_table.reset_vector));
 
     /* 続く14エントリは例外ベクタです */
     KEEP(*(.vector_table.exceptions)); /* <- 追加 */
+
+    /* 残りのエントリはデバイス固有の割り込みベクタです */
+    KEEP(*(.vector_table.interrupts));
   } > FLASH
 
   .text :
@@ -31,27 +30,84 @@ SECTIONS
     *(.text .text.*);
   } > FLASH
 
+  /* 静的コンストラクタへのポインタです。RAMの初期化後、`main`の前に実行されます */
+  .init_array : ALIGN(4)
+  {
+    __init_array_start = .;
+    KEEP(*(SORT(.init_array.*)));
+    KEEP(*(.init_array));
+    __init_array_end = .;
+  } > FLASH
+
   /* CHANGED! */
   .rodata :
   {
     *(.rodata .rodata.*);
+
+    /* `.data`の初期値をワード単位でコピーできるようにします */
+    . = ALIGN(4);
   } > FLASH
 
-  .bss :
+  /* VTORに設定するアドレスは、ベクタテーブルのサイズ以上の2のべき乗（最小で
+     128バイト）に揃える必要があります。ベクタテーブルは最大で2048バイトです */
+  _vector_table_align = SIZEOF(.vector_table) <= 128 ? 128
+    : (SIZEOF(.vector_table) <= 256 ? 256
+    : (SIZEOF(.vector_table) <= 512 ? 512
+    : (SIZEOF(.vector_table) <= 1024 ? 1024 : 2048)));
+
+  /* `ram-vectors`フィーチャでベクタテーブルをコピーする領域です。
+     `stack-below-data`フィーチャでは、この領域と`.bss`、`.data`、`.ramfunc`、
+     `.uninit`を`.boot_record`の直前に詰めて配置します */
+  .vector_table_ram (__rt_stack_below_data ? (ADDR(.boot_record) - SIZEOF(.vector_table_ram) - SIZEOF(.bss) - SIZEOF(.data) - SIZEOF(.ramfunc) - SIZEOF(.uninit)) & ~(__rt_ram_vectors ? _vector_table_align - 1 : 7) : ORIGIN(RAM)) (NOLOAD) :
+  {
+    _svector_table_ram = .;
+    . += __rt_ram_vectors ? SIZEOF(.vector_table) : 0;
+  } > RAM
+
+  .bss : ALIGN(4)
   {
     _sbss = .;
     *(.bss .bss.*);
+    . = ALIGN(4);
     _ebss = .;
   } > RAM
 
-  .data : AT(ADDR(.rodata) + SIZEOF(.rodata))
+  .data : AT(ADDR(.rodata) + SIZEOF(.rodata)) ALIGN(4)
   {
     _sdata = .;
     *(.data .data.*);
+    . = ALIGN(4);
     _edata = .;
   } > RAM
 
+  /* RAMから実行する関数です。`.data`と同様に、`Reset`がFlashからコピーします */
+  .ramfunc : AT(LOADADDR(.data) + SIZEOF(.data)) ALIGN(4)
+  {
+    _sramfunc = .;
+    *(.ramfunc .ramfunc.*);
+    . = ALIGN(4);
+    _eramfunc = .;
+  } > RAM
+
+  /* `Reset`が初期化しない領域です。ソフトリセットの前後で値が保持されます */
+  .uninit (NOLOAD) : ALIGN(4)
+  {
+    *(.uninit .uninit.*);
+    . = ALIGN(4);
+  } > RAM
+
+  /* `boot-info`フィーチャの起動記録です。`.uninit`と同様に`Reset`は触れません。
+     ブートローダとそれが起動するアプリケーションが同じアドレスを使えるよう、
+     RAMの終端に置きます。直前から伸びるスタックのため、サイズを8の倍数にします。
+     フィーチャが無効なら空です */
+  .boot_record (ORIGIN(RAM) + LENGTH(RAM) - SIZEOF(.boot_record)) (NOLOAD) :
+  {
+    *(.boot_record);
+    . = ALIGN(8);
+  } > RAM
+
   _sidata = LOADADDR(.data);
+  _siramfunc = LOADADDR(.ramfunc);
 
   /DISCARD/ :
   {
@@ -67,3 +123,125 @@ PROVIDE(UsageFault = DefaultExceptionHandler);
 PROVIDE(SVCall = DefaultExceptionHandler);
 PROVIDE(PendSV = DefaultExceptionHandler);
 PROVIDE(SysTick = DefaultExceptionHandler);
+
+/* `DefaultExceptionHandler`が例外番号とともに呼び出します。デフォルトでは
+   r0に例外番号を入れたまま、ブレークポイント（`bkpt 0xff`）で停止します */
+PROVIDE(DefaultHandler = DefaultHandler_);
+
+/* ユーザーが`pre_init!`を使わなかった場合のデフォルト */
+PROVIDE(__pre_init = DefaultPreInit);
+
+/* ユーザーが`global_watchdog!`を使わなかった場合のデフォルト（何もしません） */
+PROVIDE(GLOBAL_WATCHDOG = DEFAULT_WATCHDOG);
+
+/* ビルドスクリプトがハードフロートターゲットで1に設定します */
+PROVIDE(__rt_has_fpu = 0);
+
+/* ビルドスクリプトが`paint-stack`フィーチャで1に設定します */
+PROVIDE(__rt_paint_stack = 0);
+
+/* ビルドスクリプトが`stack-below-data`フィーチャで1に設定します */
+PROVIDE(__rt_stack_below_data = 0);
+
+/* ビルドスクリプトが`ram-vectors`フィーチャで1に設定します */
+PROVIDE(__rt_ram_vectors = 0);
+
+/* ビルドスクリプトが`alloc`フィーチャで1に設定します */
+PROVIDE(__rt_alloc = 0);
+
+/* `alloc`フィーチャでスタック用に予約するサイズです。`memory.x`で上書きできます */
+PROVIDE(_stack_size = 0x2000);
+
+/* 通常、スタックはRAMの終端（`.boot_record`の直前）から静的変数に向かって伸びます。`stack-below-data`
+   フィーチャではRAMの先頭に置かれるため、オーバーフローは静的変数を壊さず、
+   HardFaultになります。`alloc`フィーチャでは、スタックは`_stack_size`バイトに
+   制限され、残りの空き領域がヒープになります */
+_stack_start = __rt_stack_below_data
+  ? (__rt_alloc ? ORIGIN(RAM) + _stack_size : ADDR(.vector_table_ram))
+  : ADDR(.boot_record);
+_stack_end = __rt_alloc
+  ? _stack_start - _stack_size
+  : (__rt_stack_below_data ? ORIGIN(RAM) : ADDR(.uninit) + SIZEOF(.uninit));
+
+/* `relocate_vector_table`がRAMにコピーする範囲です */
+_svector_table = ADDR(.vector_table);
+_evector_table = ADDR(.vector_table) + SIZEOF(.vector_table);
+
+/* ヒープは静的変数とスタック用の予約領域の間の空き領域です。`alloc`フィーチャが
+   無効なら空です */
+_sheap = __rt_alloc
+  ? (__rt_stack_below_data ? _stack_start : ADDR(.uninit) + SIZEOF(.uninit))
+  : _stack_end;
+_eheap = __rt_alloc
+  ? (__rt_stack_below_data ? ADDR(.vector_table_ram) : _stack_end)
+  : _stack_end;
+
+/* LM3S6965のデバイス固有割り込み */
+PROVIDE(GPIOA = DefaultExceptionHandler);
+PROVIDE(GPIOB = DefaultExceptionHandler);
+PROVIDE(GPIOC = DefaultExceptionHandler);
+PROVIDE(GPIOD = DefaultExceptionHandler);
+PROVIDE(GPIOE = DefaultExceptionHandler);
+PROVIDE(UART0 = DefaultExceptionHandler);
+PROVIDE(UART1 = DefaultExceptionHandler);
+PROVIDE(SSI0 = DefaultExceptionHandler);
+PROVIDE(I2C0 = DefaultExceptionHandler);
+PROVIDE(PWM_FAULT = DefaultExceptionHandler);
+PROVIDE(PWM_GEN0 = DefaultExceptionHandler);
+PROVIDE(PWM_GEN1 = DefaultExceptionHandler);
+PROVIDE(PWM_GEN2 = DefaultExceptionHandler);
+PROVIDE(QEI0 = DefaultExceptionHandler);
+PROVIDE(ADC0_SEQ0 = DefaultExceptionHandler);
+PROVIDE(ADC0_SEQ1 = DefaultExceptionHandler);
+PROVIDE(ADC0_SEQ2 = DefaultExceptionHandler);
+PROVIDE(ADC0_SEQ3 = DefaultExceptionHandler);
+PROVIDE(WATCHDOG = DefaultExceptionHandler);
+PROVIDE(TIMER0A = DefaultExceptionHandler);
+PROVIDE(TIMER0B = DefaultExceptionHandler);
+PROVIDE(TIMER1A = DefaultExceptionHandler);
+PROVIDE(TIMER1B = DefaultExceptionHandler);
+PROVIDE(TIMER2A = DefaultExceptionHandler);
+PROVIDE(TIMER2B = DefaultExceptionHandler);
+PROVIDE(COMP0 = DefaultExceptionHandler);
+PROVIDE(COMP1 = DefaultExceptionHandler);
+PROVIDE(SYSCTL = DefaultExceptionHandler);
+PROVIDE(FLASH_CTRL = DefaultExceptionHandler);
+PROVIDE(GPIOF = DefaultExceptionHandler);
+PROVIDE(GPIOG = DefaultExceptionHandler);
+PROVIDE(UART2 = DefaultExceptionHandler);
+PROVIDE(TIMER3A = DefaultExceptionHandler);
+PROVIDE(TIMER3B = DefaultExceptionHandler);
+PROVIDE(I2C1 = DefaultExceptionHandler);
+PROVIDE(QEI1 = DefaultExceptionHandler);
+PROVIDE(ETHERNET = DefaultExceptionHandler);
+PROVIDE(HIBERNATE = DefaultExceptionHandler);
+
+/* ベクタテーブルとRAM初期化用シンボルの検査 */
+ASSERT(RESET_VECTOR == ADDR(.vector_table) + 4,
+       "ERROR(rt): the reset vector must be the second entry of the vector table");
+ASSERT(EXCEPTIONS == ADDR(.vector_table) + 8,
+       "ERROR(rt): the exception vectors must follow the reset vector");
+ASSERT(__INTERRUPTS == ADDR(.vector_table) + 0x40,
+       "ERROR(rt): the vector table must start with 16 core entries (initial SP, reset and 14 exceptions)");
+ASSERT(ADDR(.vector_table) % _vector_table_align == 0,
+       "ERROR(rt): the origin of FLASH must be aligned to the size of the vector table, rounded up to a power of two, so that VTOR can point to it");
+ASSERT(_sbss % 4 == 0 && _ebss % 4 == 0,
+       "ERROR(rt): the start and end of .bss must be 4-byte aligned");
+ASSERT(_sdata % 4 == 0 && _edata % 4 == 0,
+       "ERROR(rt): the start and end of .data must be 4-byte aligned");
+ASSERT(_sidata % 4 == 0,
+       "ERROR(rt): the load address of .data (_sidata) must be 4-byte aligned");
+ASSERT(_sidata >= ORIGIN(FLASH) && _sidata + (_edata - _sdata) <= ORIGIN(FLASH) + LENGTH(FLASH),
+       "ERROR(rt): the initial values of .data (_sidata) must be located in FLASH");
+ASSERT(_siramfunc + (_eramfunc - _sramfunc) <= ORIGIN(FLASH) + LENGTH(FLASH),
+       "ERROR(rt): the code of .ramfunc (_siramfunc) must be located in FLASH");
+ASSERT(_stack_start % 8 == 0,
+       "ERROR(rt): the initial stack pointer (_stack_start) must be 8-byte aligned");
+ASSERT(_stack_size % 8 == 0,
+       "ERROR(rt): the stack reservation (_stack_size) must be a multiple of 8 bytes");
+ASSERT(_sheap <= _eheap,
+       "ERROR(rt): not enough RAM for the stack reservation (_stack_size)");
+ASSERT(_svector_table_ram % _vector_table_align == 0 || __rt_ram_vectors == 0,
+       "ERROR(rt): the vector table in RAM must be aligned to the size of the vector table, rounded up to a power of two");
+ASSERT(SIZEOF(.vector_table) <= _vector_table_align,
+       "ERROR(rt): the vector table must not be larger than 2048 bytes");
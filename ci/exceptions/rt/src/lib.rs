@@ -1,26 +1,35 @@
+#![cfg_attr(feature = "alloc", feature(alloc_error_handler))]
 #![no_std]
 
 use core::panic::PanicInfo;
 use core::ptr;
 
+// `asm.s`で実装されているリセットハンドラです。Rustのコードを実行する前に
+// `.bss`のゼロクリアと`.data`の初期化をワード単位で行います
+extern "C" {
+    fn Reset() -> !;
+}
+
+// RAMの初期化後に、アセンブリの`Reset`だけがシンボル名で呼び出します
 #[no_mangle]
-pub unsafe extern "C" fn Reset() -> ! {
-    // 追加!
-    // RAMの初期化
-    extern "C" {
-        static mut _sbss: u8;
-        static mut _ebss: u8;
+unsafe extern "C" fn MainTrampoline() -> ! {
+    #[cfg(feature = "boot-info")]
+    record_boot();
 
-        static mut _sdata: u8;
-        static mut _edata: u8;
-        static _sidata: u8;
-    }
+    #[cfg(feature = "alloc")]
+    HEAP.init();
 
-    let count = &_ebss as *const u8 as usize - &_sbss as *const u8 as usize;
-    ptr::write_bytes(&mut _sbss as *mut u8, 0, count);
+    // `.init_array`にある静的コンストラクタを順に呼び出します
+    extern "C" {
+        static __init_array_start: unsafe extern "C" fn();
+        static __init_array_end: unsafe extern "C" fn();
+    }
 
-    let count = &_edata as *const u8 as usize - &_sdata as *const u8 as usize;
-    ptr::copy_nonoverlapping(&_sidata as *const u8, &mut _sdata as *mut u8, count);
+    let mut ctor = ptr::addr_of!(__init_array_start);
+    while ctor < ptr::addr_of!(__init_array_end) {
+        (*ctor)();
+        ctor = ctor.add(1);
+    }
 
     // ユーザーエントリポイントを呼び出します
     extern "Rust" {
@@ -35,8 +44,69 @@ pub unsafe extern "C" fn Reset() -> ! {
 #[no_mangle]
 pub static RESET_VECTOR: unsafe extern "C" fn() -> ! = Reset;
 
+// パニック時の動作は`panic-*`フィーチャ（か`test-harness`フィーチャ）で1つだけ選びます
+#[cfg(any(
+    all(
+        feature = "panic-halt",
+        any(
+            feature = "panic-semihosting",
+            feature = "panic-bkpt",
+            feature = "panic-reset",
+            feature = "test-harness"
+        )
+    ),
+    all(
+        feature = "panic-semihosting",
+        any(
+            feature = "panic-bkpt",
+            feature = "panic-reset",
+            feature = "test-harness"
+        )
+    ),
+    all(
+        feature = "panic-bkpt",
+        any(feature = "panic-reset", feature = "test-harness")
+    ),
+    all(feature = "panic-reset", feature = "test-harness"),
+))]
+compile_error!("only one of the `panic-*` and `test-harness` features can be enabled");
+
+// その場で停止します
+#[cfg(feature = "panic-halt")]
+#[panic_handler]
+fn panic(_panic: &PanicInfo<'_>) -> ! {
+    loop {}
+}
+
+// メッセージをホストに表示し、QEMUを失敗として終了させます
+#[cfg(feature = "panic-semihosting")]
+#[panic_handler]
+fn panic(panic: &PanicInfo<'_>) -> ! {
+    use cortex_m_semihosting::{debug, hprintln};
+
+    hprintln!("{}", panic).ok();
+    debug::exit(debug::EXIT_FAILURE);
+
+    loop {}
+}
+
+// ブレークポイント（`bkpt 0xfe`）でデバッガに制御を移します
+#[cfg(feature = "panic-bkpt")]
+#[panic_handler]
+fn panic(_panic: &PanicInfo<'_>) -> ! {
+    extern "C" {
+        fn __rt_panic_bkpt() -> !;
+    }
+
+    unsafe { __rt_panic_bkpt() }
+}
+
+// SCB->AIRCRのSYSRESETREQビットでシステムリセットを要求します
+#[cfg(feature = "panic-reset")]
 #[panic_handler]
 fn panic(_panic: &PanicInfo<'_>) -> ! {
+    unsafe { ptr::write_volatile(0xE000_ED0C as *mut u32, 0x05FA_0004) }
+
     loop {}
 }
 
@@ -45,30 +115,65 @@ macro_rules! entry {
     ($path:path) => {
         #[export_name = "main"]
         pub unsafe fn __main() -> ! {
+            // 与えられたパスの型チェック（`fn() -> !`か`fn() -> Result<(), E>`）
+            let f: fn() -> _ = $path;
+
+            $crate::Termination::start(f)
+        }
+    };
+    // `main`は一度しか呼ばれず、戻ってこないため、ここで宣言した`static mut`変数への
+    // `&'static mut`参照を安全に渡すことができます
+    ($path:path, { $(static mut $name:ident: $ty:ty = $init:expr;)* }) => {
+        #[export_name = "main"]
+        pub unsafe fn __main() -> ! {
+            $(static mut $name: $ty = $init;)*
+
             // 与えられたパスの型チェック
-            let f: fn() -> ! = $path;
+            let f: fn($(&'static mut $ty),*) -> ! = $path;
+
+            f($(&mut *::core::ptr::addr_of_mut!($name)),*)
+        }
+    };
+}
+
+// `Reset`が`bl __pre_init`で呼び出すため、C ABIを使います
+#[allow(non_snake_case)]
+#[no_mangle]
+pub extern "C" fn DefaultPreInit() {}
+
+#[macro_export]
+macro_rules! pre_init {
+    ($path:path) => {
+        #[export_name = "__pre_init"]
+        pub unsafe extern "C" fn __pre_init() {
+            // 与えられたパスの型チェック（`Reset`から呼ばれるのはこのC ABIのラッパーです）
+            let f: unsafe fn() = $path;
 
             f()
         }
-    }
+    };
 }
 
 pub union Vector {
-    reserved: u32,
-    handler: unsafe extern "C" fn(),
+    pub reserved: u32,
+    pub handler: unsafe extern "C" fn(),
 }
 
 extern "C" {
     fn NMI();
     fn HardFault();
+    #[cfg(not(armv6m))]
     fn MemManage();
+    #[cfg(not(armv6m))]
     fn BusFault();
+    #[cfg(not(armv6m))]
     fn UsageFault();
     fn SVCall();
     fn PendSV();
     fn SysTick();
 }
 
+#[cfg(not(armv6m))]
 #[link_section = ".vector_table.exceptions"]
 #[no_mangle]
 pub static EXCEPTIONS: [Vector; 14] = [
@@ -90,7 +195,803 @@ pub static EXCEPTIONS: [Vector; 14] = [
     Vector { handler: SysTick },
 ];
 
+// ARMv6-Mでは、MemManage、BusFault、UsageFaultのスロットは予約済みです
+#[cfg(armv6m)]
+#[link_section = ".vector_table.exceptions"]
+#[no_mangle]
+pub static EXCEPTIONS: [Vector; 14] = [
+    Vector { handler: NMI },
+    Vector { handler: HardFault },
+    Vector { reserved: 0 },
+    Vector { reserved: 0 },
+    Vector { reserved: 0 },
+    Vector { reserved: 0 },
+    Vector { reserved: 0 },
+    Vector { reserved: 0 },
+    Vector { reserved: 0 },
+    Vector { handler: SVCall },
+    Vector { reserved: 0 },
+    Vector { reserved: 0 },
+    Vector { handler: PendSV },
+    Vector { handler: SysTick },
+];
+
 #[no_mangle]
 pub extern "C" fn DefaultExceptionHandler() {
-    loop {}
+    // SCB->ICSRのVECTACTIVEフィールドから、発生した例外の番号を読み出します
+    const SCB_ICSR: *const u32 = 0xE000_ED04 as *const u32;
+    let vectactive = unsafe { ptr::read_volatile(SCB_ICSR) } & 0x1FF;
+
+    // デバイス固有の割り込みが0になるように、コア例外は負の値になります
+    extern "C" {
+        fn DefaultHandler(irqn: i16);
+    }
+
+    unsafe { DefaultHandler(vectactive as i16 - 16) }
+}
+
+// LM3S6965のデバイス固有割り込み
+#[cfg(not(feature = "device"))]
+extern "C" {
+    fn GPIOA();
+    fn GPIOB();
+    fn GPIOC();
+    fn GPIOD();
+    fn GPIOE();
+    fn UART0();
+    fn UART1();
+    fn SSI0();
+    fn I2C0();
+    fn PWM_FAULT();
+    fn PWM_GEN0();
+    fn PWM_GEN1();
+    fn PWM_GEN2();
+    fn QEI0();
+    fn ADC0_SEQ0();
+    fn ADC0_SEQ1();
+    fn ADC0_SEQ2();
+    fn ADC0_SEQ3();
+    fn WATCHDOG();
+    fn TIMER0A();
+    fn TIMER0B();
+    fn TIMER1A();
+    fn TIMER1B();
+    fn TIMER2A();
+    fn TIMER2B();
+    fn COMP0();
+    fn COMP1();
+    fn SYSCTL();
+    fn FLASH_CTRL();
+    fn GPIOF();
+    fn GPIOG();
+    fn UART2();
+    fn TIMER3A();
+    fn TIMER3B();
+    fn I2C1();
+    fn QEI1();
+    fn ETHERNET();
+    fn HIBERNATE();
+}
+
+// `device`フィーチャを有効にすると、このテーブルの代わりにデバイスクレートが
+// 任意の長さの`__INTERRUPTS`を`.vector_table.interrupts`セクションに配置します
+#[cfg(not(feature = "device"))]
+#[link_section = ".vector_table.interrupts"]
+#[no_mangle]
+pub static __INTERRUPTS: [Vector; 44] = [
+    Vector { handler: GPIOA },
+    Vector { handler: GPIOB },
+    Vector { handler: GPIOC },
+    Vector { handler: GPIOD },
+    Vector { handler: GPIOE },
+    Vector { handler: UART0 },
+    Vector { handler: UART1 },
+    Vector { handler: SSI0 },
+    Vector { handler: I2C0 },
+    Vector { handler: PWM_FAULT },
+    Vector { handler: PWM_GEN0 },
+    Vector { handler: PWM_GEN1 },
+    Vector { handler: PWM_GEN2 },
+    Vector { handler: QEI0 },
+    Vector { handler: ADC0_SEQ0 },
+    Vector { handler: ADC0_SEQ1 },
+    Vector { handler: ADC0_SEQ2 },
+    Vector { handler: ADC0_SEQ3 },
+    Vector { handler: WATCHDOG },
+    Vector { handler: TIMER0A },
+    Vector { handler: TIMER0B },
+    Vector { handler: TIMER1A },
+    Vector { handler: TIMER1B },
+    Vector { handler: TIMER2A },
+    Vector { handler: TIMER2B },
+    Vector { handler: COMP0 },
+    Vector { handler: COMP1 },
+    Vector { reserved: 0 },
+    Vector { handler: SYSCTL },
+    Vector { handler: FLASH_CTRL },
+    Vector { handler: GPIOF },
+    Vector { handler: GPIOG },
+    Vector { reserved: 0 },
+    Vector { handler: UART2 },
+    Vector { reserved: 0 },
+    Vector { handler: TIMER3A },
+    Vector { handler: TIMER3B },
+    Vector { handler: I2C1 },
+    Vector { handler: QEI1 },
+    Vector { reserved: 0 },
+    Vector { reserved: 0 },
+    Vector { reserved: 0 },
+    Vector { handler: ETHERNET },
+    Vector { handler: HIBERNATE },
+];
+
+// `exception!`マクロが受け付ける例外の名前
+#[allow(non_camel_case_types)]
+pub enum Exception {
+    NMI,
+    HardFault,
+    #[cfg(not(armv6m))]
+    MemManage,
+    #[cfg(not(armv6m))]
+    BusFault,
+    #[cfg(not(armv6m))]
+    UsageFault,
+    SVCall,
+    PendSV,
+    SysTick,
+}
+
+#[macro_export]
+macro_rules! exception {
+    (HardFault, $path:path) => {
+        #[allow(non_snake_case)]
+        #[no_mangle]
+        pub unsafe extern "C" fn HardFault() -> ! {
+            // 与えられたパスの型チェック
+            let f: fn() -> ! = $path;
+
+            f()
+        }
+    };
+    (DefaultHandler, $path:path) => {
+        #[allow(non_snake_case)]
+        #[no_mangle]
+        pub unsafe extern "C" fn DefaultHandler(irqn: i16) {
+            // 与えられたパスの型チェック
+            let f: fn(i16) = $path;
+
+            f(irqn)
+        }
+    };
+    ($Name:ident, $path:path) => {
+        #[allow(non_snake_case)]
+        #[no_mangle]
+        pub unsafe extern "C" fn $Name() {
+            // 例外の名前のチェック
+            let _ = $crate::Exception::$Name;
+
+            // 与えられたパスの型チェック
+            let f: fn() = $path;
+
+            f()
+        }
+    };
+}
+
+// `paint-stack`フィーチャを有効にすると、`Reset`はスタックの底（`_stack_end`）から
+// スタックポインタまでの領域をこのパターンで塗りつぶします
+#[cfg(feature = "paint-stack")]
+pub const STACK_PAINT: u32 = 0xCCCC_CCCC;
+
+// これまでに使われたスタックの最大量をバイト単位で返します
+#[cfg(feature = "paint-stack")]
+pub fn stack_high_water_mark() -> usize {
+    extern "C" {
+        static _stack_end: u32;
+        static _stack_start: u32;
+    }
+
+    unsafe {
+        let end = ptr::addr_of!(_stack_start);
+        let mut p = ptr::addr_of!(_stack_end);
+
+        // パターンが上書きされている最初のワードを探します
+        while p < end && ptr::read_volatile(p) == STACK_PAINT {
+            p = p.add(1);
+        }
+
+        end as usize - p as usize
+    }
+}
+
+// `Reset`が初期化しない`.uninit`セクションに`MaybeUninit`の静的変数を宣言します。
+// ソフトリセットの後も値は残りますが、電源投入直後の値は不定です
+#[macro_export]
+macro_rules! uninit {
+    ($(#[$attr:meta])* $vis:vis static mut $name:ident: $ty:ty;) => {
+        $(#[$attr])*
+        #[link_section = ".uninit"]
+        $vis static mut $name: ::core::mem::MaybeUninit<$ty> =
+            ::core::mem::MaybeUninit::uninit();
+    };
+}
+
+// 割り込みを禁止した状態で`f`を実行します
+#[cfg(any(feature = "alloc", all(feature = "ram-vectors", not(armv6m))))]
+fn interrupt_free<R>(f: impl FnOnce() -> R) -> R {
+    extern "C" {
+        fn __rt_interrupt_disable() -> u32;
+        fn __rt_interrupt_restore(primask: u32);
+    }
+
+    unsafe {
+        let primask = __rt_interrupt_disable();
+        let r = f();
+        __rt_interrupt_restore(primask);
+        r
+    }
+}
+
+// `_sheap`から`_eheap`までの領域を使うグローバルアロケータです
+#[cfg(feature = "alloc")]
+struct Heap(core::cell::UnsafeCell<linked_list_allocator::Heap>);
+
+#[cfg(feature = "alloc")]
+unsafe impl Sync for Heap {}
+
+#[cfg(feature = "alloc")]
+impl Heap {
+    // `MainTrampoline`が`main`の前に一度だけ呼び出します
+    unsafe fn init(&self) {
+        extern "C" {
+            static mut _sheap: u8;
+            static mut _eheap: u8;
+        }
+
+        let start = ptr::addr_of_mut!(_sheap);
+        let end = ptr::addr_of_mut!(_eheap);
+        (*self.0.get()).init(start, end as usize - start as usize);
+    }
+}
+
+#[cfg(feature = "alloc")]
+unsafe impl core::alloc::GlobalAlloc for Heap {
+    unsafe fn alloc(&self, layout: core::alloc::Layout) -> *mut u8 {
+        interrupt_free(|| match (*self.0.get()).allocate_first_fit(layout) {
+            Ok(p) => p.as_ptr(),
+            Err(()) => ptr::null_mut(),
+        })
+    }
+
+    unsafe fn dealloc(&self, p: *mut u8, layout: core::alloc::Layout) {
+        interrupt_free(|| (*self.0.get()).deallocate(core::ptr::NonNull::new_unchecked(p), layout))
+    }
+}
+
+#[cfg(feature = "alloc")]
+#[global_allocator]
+static HEAP: Heap = Heap(core::cell::UnsafeCell::new(
+    linked_list_allocator::Heap::empty(),
+));
+
+// メモリの確保に失敗した場合は、パニックハンドラに処理を任せます
+#[cfg(feature = "alloc")]
+#[alloc_error_handler]
+fn alloc_error(layout: core::alloc::Layout) -> ! {
+    panic!("memory allocation of {} bytes failed", layout.size())
+}
+
+// ベクタテーブルにおける各例外の位置
+#[cfg(all(feature = "ram-vectors", not(armv6m)))]
+impl Exception {
+    fn nr(&self) -> usize {
+        match self {
+            Exception::NMI => 2,
+            Exception::HardFault => 3,
+            Exception::MemManage => 4,
+            Exception::BusFault => 5,
+            Exception::UsageFault => 6,
+            Exception::SVCall => 11,
+            Exception::PendSV => 14,
+            Exception::SysTick => 15,
+        }
+    }
+}
+
+// SCB->VTOR。ARMv6-MのCortex-M0にはこのレジスタがありません
+#[cfg(not(armv6m))]
+const SCB_VTOR: *mut u32 = 0xE000_ED08 as *mut u32;
+
+// RAM上のベクタテーブル（`link.x`の`.vector_table_ram`）の先頭です
+#[cfg(all(feature = "ram-vectors", not(armv6m)))]
+fn vector_table_ram() -> *mut u32 {
+    extern "C" {
+        static mut _svector_table_ram: u32;
+    }
+
+    ptr::addr_of_mut!(_svector_table_ram)
+}
+
+// FlashにあるベクタテーブルをRAMにコピーし、VTORをRAM上のコピーに向けます
+#[cfg(all(feature = "ram-vectors", not(armv6m)))]
+pub fn relocate_vector_table() {
+    extern "C" {
+        static _svector_table: u32;
+        static _evector_table: u32;
+
+        fn __rt_barrier();
+    }
+
+    interrupt_free(|| unsafe {
+        let mut src = ptr::addr_of!(_svector_table);
+        let end = ptr::addr_of!(_evector_table);
+        let mut dst = vector_table_ram();
+        while src < end {
+            ptr::write_volatile(dst, ptr::read_volatile(src));
+            src = src.add(1);
+            dst = dst.add(1);
+        }
+
+        ptr::write_volatile(SCB_VTOR, vector_table_ram() as u32);
+        // 以降の例外が新しいベクタテーブルを使うよう、書き込みの完了を待ちます
+        __rt_barrier();
+    })
+}
+
+// RAM上のベクタテーブルにある`exception`のハンドラを`handler`に置き換えます。
+// 先に`relocate_vector_table`を呼び出しておく必要があります
+#[cfg(all(feature = "ram-vectors", not(armv6m)))]
+pub fn set_handler(exception: Exception, handler: unsafe extern "C" fn()) {
+    interrupt_free(|| unsafe {
+        assert!(
+            ptr::read_volatile(SCB_VTOR) == vector_table_ram() as u32,
+            "the vector table hasn't been relocated to RAM"
+        );
+
+        let slot = vector_table_ram().add(exception.nr());
+        ptr::write_volatile(slot, handler as usize as u32);
+    })
+}
+
+/// `vector_table_addr`にあるベクタテーブルを持つ別のイメージ（ブートローダから
+/// 起動するアプリケーションなど）に制御を移します。ワード0をMSPに、ワード1を
+/// リセットベクタとして使います
+///
+/// # Safety
+///
+/// - `vector_table_addr`には有効なベクタテーブルがなければなりません
+/// - そのワード0は有効な初期スタックポインタ、ワード1は有効な`Reset`ハンドラでなければなりません
+/// - 割り込みと、割り込みを発生させる周辺機器を止めておかなければなりません
+pub unsafe fn jump_to(vector_table_addr: u32) -> ! {
+    extern "C" {
+        fn __rt_barrier();
+        fn __rt_jump_to(vector_table_addr: u32) -> !;
+    }
+
+    // 移し先のイメージが、この起動を記録し直さないようにします
+    #[cfg(feature = "boot-info")]
+    {
+        let record = ptr::addr_of_mut!(BOOT_RECORD).cast::<BootRecord>();
+        ptr::write_volatile(ptr::addr_of_mut!((*record).handoff), vector_table_addr);
+    }
+
+    // ARMv6-MのCortex-M0にはVTORがありません
+    #[cfg(not(armv6m))]
+    {
+        ptr::write_volatile(SCB_VTOR, vector_table_addr);
+        // 新しいSPとPCを読み込む前に、VTORへの書き込みの完了を待ちます
+        __rt_barrier();
+    }
+
+    __rt_jump_to(vector_table_addr)
+}
+
+// `.ramfunc`セクションに置かれ、RAMから実行される関数を定義します。Flashの
+// 書き換え中など、Flashから命令を読み出せないときに使います。この関数から呼び出す
+// 関数はFlashに置かれたままであることに注意してください
+#[macro_export]
+macro_rules! ramfunc {
+    ($(#[$attr:meta])* $vis:vis fn $name:ident($($arg:ident: $ty:ty),*) $(-> $ret:ty)? $body:block) => {
+        $(#[$attr])*
+        #[inline(never)]
+        #[link_section = ".ramfunc"]
+        $vis fn $name($($arg: $ty),*) $(-> $ret)? $body
+    };
+}
+
+// リセットの要因
+#[cfg(feature = "boot-info")]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ResetCause {
+    PowerOn,
+    BrownOut,
+    External,
+    Watchdog,
+    Software,
+    Lockup,
+    Unknown,
+}
+
+// リセット要因レジスタを持つデバイスが実装します
+#[cfg(feature = "boot-info")]
+pub trait ResetCauseSource: Sync {
+    // リセット要因を読み出し、次のリセットに備えてクリアします
+    fn read_and_clear(&self) -> ResetCause;
+}
+
+// `RESET_CAUSE_SOURCE`を定義します。`device`フィーチャを有効にした場合、
+// デバイスクレートがこのマクロを使います
+#[cfg(feature = "boot-info")]
+#[macro_export]
+macro_rules! reset_cause_source {
+    ($source:expr) => {
+        #[no_mangle]
+        pub static RESET_CAUSE_SOURCE: &dyn $crate::ResetCauseSource = &$source;
+    };
+}
+
+// LM3S6965のリセット要因レジスタ（SYSCTL->RESC）
+#[cfg(all(feature = "boot-info", not(feature = "device")))]
+pub struct Lm3s6965;
+
+#[cfg(all(feature = "boot-info", not(feature = "device")))]
+impl ResetCauseSource for Lm3s6965 {
+    fn read_and_clear(&self) -> ResetCause {
+        const SYSCTL_RESC: *mut u32 = 0x400F_E05C as *mut u32;
+
+        unsafe {
+            let resc = ptr::read_volatile(SYSCTL_RESC);
+            ptr::write_volatile(SYSCTL_RESC, 0);
+
+            // 複数のビットが立っている場合は、より根本的な要因を優先します
+            if resc & (1 << 1) != 0 {
+                ResetCause::PowerOn
+            } else if resc & (1 << 2 | 1 << 5) != 0 {
+                // BOR（ブラウンアウト）とLDO（内部レギュレータの異常）
+                ResetCause::BrownOut
+            } else if resc & (1 << 3) != 0 {
+                ResetCause::Watchdog
+            } else if resc & (1 << 4) != 0 {
+                ResetCause::Software
+            } else if resc & (1 << 0) != 0 {
+                ResetCause::External
+            } else {
+                ResetCause::Unknown
+            }
+        }
+    }
+}
+
+#[cfg(all(feature = "boot-info", not(feature = "device")))]
+reset_cause_source!(Lm3s6965);
+
+// `main`から参照できる起動情報
+#[cfg(feature = "boot-info")]
+#[derive(Clone, Copy, Debug)]
+pub struct BootInfo {
+    // 直前のリセットの要因
+    pub reset_cause: ResetCause,
+    // 電源投入からの起動回数（電源投入直後は1）
+    pub boot_count: u32,
+}
+
+#[cfg(feature = "boot-info")]
+#[repr(C)]
+struct BootRecord {
+    magic: u32,
+    // `system_reset`がリセットを要求する前に書き込みます
+    reset_requested: u32,
+    // `jump_to`が制御を移す前に、移し先のベクタテーブルのアドレスを書き込みます
+    handoff: u32,
+    info: BootInfo,
+}
+
+// 電源投入直後の不定値と、記録済みの値を区別するためのマジックナンバー
+#[cfg(feature = "boot-info")]
+const BOOT_RECORD_MAGIC: u32 = 0xB007_1AF0;
+
+// `jump_to`を経ずに起動したことを表す`handoff`の値です。ベクタテーブルは境界に
+// 揃っているため、このアドレスに置かれることはありません
+#[cfg(feature = "boot-info")]
+const NO_HANDOFF: u32 = 0xFFFF_FFFF;
+
+// `link.x`がRAMの終端に置くため、ブートローダとアプリケーションで共有されます
+#[cfg(feature = "boot-info")]
+#[link_section = ".boot_record"]
+static mut BOOT_RECORD: core::mem::MaybeUninit<BootRecord> = core::mem::MaybeUninit::uninit();
+
+// `MainTrampoline`が起動のたびに一度だけ呼び出します
+#[cfg(feature = "boot-info")]
+fn record_boot() {
+    extern "Rust" {
+        static RESET_CAUSE_SOURCE: &'static dyn ResetCauseSource;
+    }
+
+    extern "C" {
+        static _svector_table: u32;
+    }
+
+    unsafe {
+        let record = ptr::addr_of_mut!(BOOT_RECORD).cast::<BootRecord>();
+
+        let valid = ptr::read_volatile(ptr::addr_of!((*record).magic)) == BOOT_RECORD_MAGIC;
+
+        // ブートローダが既にこの起動を記録し、`jump_to`でこのイメージに制御を移しています。
+        // 移し先がこのイメージのベクタテーブルでなければ、以前の起動の書き込みが残っているだけです
+        if valid
+            && ptr::read_volatile(ptr::addr_of!((*record).handoff))
+                == ptr::addr_of!(_svector_table) as u32
+        {
+            ptr::write_volatile(ptr::addr_of_mut!((*record).handoff), NO_HANDOFF);
+            return;
+        }
+
+        let requested = valid && ptr::read_volatile(ptr::addr_of!((*record).reset_requested)) != 0;
+
+        // リセット要因レジスタがソフトウェアリセットを報告しないデバイス（QEMUなど）でも、
+        // `system_reset`によるリセットは`Software`として記録します
+        let reset_cause = match RESET_CAUSE_SOURCE.read_and_clear() {
+            ResetCause::Unknown if requested => ResetCause::Software,
+            cause => cause,
+        };
+
+        let boot_count = if valid && reset_cause != ResetCause::PowerOn {
+            ptr::read_volatile(ptr::addr_of!((*record).info.boot_count)) + 1
+        } else {
+            1
+        };
+
+        ptr::write_volatile(
+            record,
+            BootRecord {
+                magic: BOOT_RECORD_MAGIC,
+                reset_requested: 0,
+                handoff: NO_HANDOFF,
+                info: BootInfo {
+                    reset_cause,
+                    boot_count,
+                },
+            },
+        );
+    }
+}
+
+// 直前のリセットの要因と起動回数を返します。ブートローダが`jump_to`で起動した
+// イメージでは、ブートローダが記録した値を返します
+#[cfg(feature = "boot-info")]
+pub fn boot_info() -> Option<BootInfo> {
+    unsafe {
+        let record = ptr::addr_of!(BOOT_RECORD).cast::<BootRecord>();
+        if ptr::read_volatile(ptr::addr_of!((*record).magic)) != BOOT_RECORD_MAGIC {
+            return None;
+        }
+
+        Some(ptr::read_volatile(ptr::addr_of!((*record).info)))
+    }
+}
+
+// SCB->AIRCRのSYSRESETREQビットでシステムリセットを要求します。`boot-info`フィーチャ
+// では、リセット後に`ResetCause::Software`が記録されるよう、要求したことを記録します
+pub fn system_reset() -> ! {
+    extern "C" {
+        fn __rt_barrier();
+    }
+
+    unsafe {
+        #[cfg(feature = "boot-info")]
+        {
+            let record = ptr::addr_of_mut!(BOOT_RECORD).cast::<BootRecord>();
+            ptr::write_volatile(ptr::addr_of_mut!((*record).reset_requested), 1);
+        }
+
+        // リセットの前に、`.uninit`などへの書き込みを完了させます
+        __rt_barrier();
+        ptr::write_volatile(0xE000_ED0C as *mut u32, 0x05FA_0004);
+        __rt_barrier();
+    }
+
+    // リセットが実際に起こるまで待ちます
+    loop {
+        core::hint::spin_loop()
+    }
+}
+
+// `Reset`の実行中にも動いているウォッチドッグ（ブートROMが起動したものなど）です。
+// `feed`は`.bss`と`.data`の初期化中にも呼び出されるため、静的変数を使ってはいけません
+pub trait Watchdog: Sync {
+    fn feed(&self);
+}
+
+// `GLOBAL_WATCHDOG`を定義します。LM3S6965の`WATCHDOG`割り込みとは別のシンボルです
+#[macro_export]
+macro_rules! global_watchdog {
+    ($watchdog:expr) => {
+        #[no_mangle]
+        pub static GLOBAL_WATCHDOG: &dyn $crate::Watchdog = &$watchdog;
+    };
+}
+
+struct NoWatchdog;
+
+impl Watchdog for NoWatchdog {
+    fn feed(&self) {}
+}
+
+#[no_mangle]
+pub static DEFAULT_WATCHDOG: &dyn Watchdog = &NoWatchdog;
+
+// 登録されたウォッチドッグに餌を与えます。アイドルループなどから呼び出します
+pub fn feed_watchdog() {
+    extern "Rust" {
+        static GLOBAL_WATCHDOG: &'static dyn Watchdog;
+    }
+
+    unsafe { GLOBAL_WATCHDOG.feed() }
+}
+
+// `Reset`がRAMを1KiB初期化するごとに呼び出します
+#[no_mangle]
+pub extern "C" fn __rt_feed_watchdog() {
+    feed_watchdog()
+}
+
+// `entry!`が受け付ける`main`の型です。`fn() -> Result<(), E>`を受け付けるには
+// `semihosting`フィーチャが必要です
+pub trait Termination {
+    fn start(self) -> !;
+}
+
+impl Termination for fn() -> ! {
+    fn start(self) -> ! {
+        self()
+    }
+}
+
+// `Ok`ならQEMUを成功として、`Err`ならエラーを表示して失敗として終了させます
+#[cfg(feature = "semihosting")]
+impl<E> Termination for fn() -> Result<(), E>
+where
+    E: core::fmt::Debug,
+{
+    fn start(self) -> ! {
+        use cortex_m_semihosting::{debug, hprintln};
+
+        match self() {
+            Ok(()) => debug::exit(debug::EXIT_SUCCESS),
+            Err(e) => {
+                hprintln!("Error: {:?}", e).ok();
+                debug::exit(debug::EXIT_FAILURE);
+            }
+        }
+
+        loop {
+            core::hint::spin_loop()
+        }
+    }
+}
+
+// テストハーネスの進行状況。パニックしたテストからはシステムリセットで抜け出し、
+// 次のテストから再開するため、`.uninit`に置きます
+#[cfg(feature = "test-harness")]
+#[repr(C)]
+struct TestRecord {
+    magic: u32,
+    // 別のテストバイナリの記録と区別するため、テストの数も記録します
+    count: usize,
+    next: usize,
+    failed: usize,
+}
+
+#[cfg(feature = "test-harness")]
+const TEST_RECORD_MAGIC: u32 = 0x7E57_CA5E;
+
+#[cfg(feature = "test-harness")]
+uninit! {
+    static mut TEST_RECORD: TestRecord;
+}
+
+// `#[test_case]`で集められるテストです
+#[cfg(feature = "test-harness")]
+pub trait Testable {
+    fn run(&self);
+}
+
+#[cfg(feature = "test-harness")]
+impl<T> Testable for T
+where
+    T: Fn(),
+{
+    fn run(&self) {
+        use cortex_m_semihosting::{hprint, hprintln};
+
+        hprint!("test {} ... ", core::any::type_name::<T>()).ok();
+        self();
+        hprintln!("ok").ok();
+    }
+}
+
+// `#![test_runner(rt::test_runner)]`で指定するテストランナーです。各テストを順に実行し、
+// 全て成功すればQEMUを成功として、1つでも失敗すれば失敗として終了させます
+#[cfg(feature = "test-harness")]
+pub fn test_runner(tests: &[&dyn Testable]) -> ! {
+    use cortex_m_semihosting::{debug, hprintln};
+
+    unsafe {
+        let record = ptr::addr_of_mut!(TEST_RECORD).cast::<TestRecord>();
+
+        let resume = ptr::read_volatile(ptr::addr_of!((*record).magic)) == TEST_RECORD_MAGIC
+            && ptr::read_volatile(ptr::addr_of!((*record).count)) == tests.len();
+        if !resume {
+            hprintln!("running {} tests", tests.len()).ok();
+
+            ptr::write_volatile(
+                record,
+                TestRecord {
+                    magic: TEST_RECORD_MAGIC,
+                    count: tests.len(),
+                    next: 0,
+                    failed: 0,
+                },
+            );
+        }
+
+        loop {
+            let next = ptr::read_volatile(ptr::addr_of!((*record).next));
+            if next >= tests.len() {
+                break;
+            }
+
+            // パニックした場合に次のテストから再開できるよう、実行する前に進めておきます
+            ptr::write_volatile(ptr::addr_of_mut!((*record).next), next + 1);
+            tests[next].run();
+        }
+
+        let failed = ptr::read_volatile(ptr::addr_of!((*record).failed));
+        ptr::write_volatile(ptr::addr_of_mut!((*record).magic), 0);
+
+        let passed = tests.len() - failed;
+        if failed == 0 {
+            hprintln!("\ntest result: ok. {} passed; 0 failed", passed).ok();
+            debug::exit(debug::EXIT_SUCCESS);
+        } else {
+            hprintln!(
+                "\ntest result: FAILED. {} passed; {} failed",
+                passed,
+                failed
+            )
+            .ok();
+            debug::exit(debug::EXIT_FAILURE);
+        }
+    }
+
+    loop {
+        core::hint::spin_loop()
+    }
+}
+
+// テストの実行中なら失敗を記録してシステムリセットを要求します。リセット後、`main`から
+// 呼ばれた`test_runner`が次のテストから再開します
+#[cfg(feature = "test-harness")]
+#[panic_handler]
+fn panic(panic: &PanicInfo<'_>) -> ! {
+    use cortex_m_semihosting::{debug, hprintln};
+
+    hprintln!("FAILED\n{}", panic).ok();
+
+    unsafe {
+        let record = ptr::addr_of_mut!(TEST_RECORD).cast::<TestRecord>();
+
+        if ptr::read_volatile(ptr::addr_of!((*record).magic)) == TEST_RECORD_MAGIC {
+            let failed = ptr::read_volatile(ptr::addr_of!((*record).failed));
+            ptr::write_volatile(ptr::addr_of_mut!((*record).failed), failed + 1);
+
+            ptr::write_volatile(0xE000_ED0C as *mut u32, 0x05FA_0004);
+        } else {
+            debug::exit(debug::EXIT_FAILURE);
+        }
+    }
+
+    loop {
+        core::hint::spin_loop()
+    }
 }
@@ -11,7 +11,7 @@ main() {
     curl -LSfs https://japaric.github.io/trust/install.sh | \
         sh -s -- --git rust-lang-nursery/mdbook --tag $tag
 
-    rustup target add thumbv7m-none-eabi
+    rustup target add thumbv6m-none-eabi thumbv7m-none-eabi thumbv7em-none-eabihf
 
     # install arm-none-eabi-gcc
     mkdir gcc
@@ -0,0 +1,7 @@
+/* LM3S6965マイクロコントローラのメモリレイアウト */
+/* 1K = 1 KiBi = 1024バイト */
+MEMORY
+{
+  FLASH : ORIGIN = 0x00000000, LENGTH = 256K
+  RAM : ORIGIN = 0x20000000, LENGTH = 64K
+}
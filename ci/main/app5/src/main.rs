@@ -0,0 +1,24 @@
+#![no_main]
+#![no_std]
+
+use core::ptr;
+
+use rt::{entry, pre_init};
+
+entry!(main);
+
+pre_init!(before_ram_init);
+
+static mut DATA: i32 = 1;
+
+unsafe fn before_ram_init() {
+    // ここで外部RAMの有効化やウォッチドッグの設定を行います
+    // `.bss`と`.data`はまだ初期化されていないため、`static`変数を使ってはいけません
+}
+
+fn main() -> ! {
+    // `pre_init!`のフックが実行された後でも、DATAは正しく初期化されています
+    let _x = unsafe { ptr::read_volatile(ptr::addr_of!(DATA)) };
+
+    loop {}
+}
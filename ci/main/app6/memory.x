@@ -0,0 +1,6 @@
+/* MPS2-AN386（Cortex-M4F）のメモリレイアウト */
+MEMORY
+{
+  FLASH : ORIGIN = 0x00000000, LENGTH = 4M
+  RAM : ORIGIN = 0x20000000, LENGTH = 4M
+}
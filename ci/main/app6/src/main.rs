@@ -0,0 +1,25 @@
+#![no_main]
+#![no_std]
+
+use core::ptr;
+
+use cortex_m_semihosting::debug;
+
+use rt::entry;
+
+entry!(main);
+
+static mut X: f32 = 1.5;
+
+fn main() -> ! {
+    // FPUが有効化されていなければ、最初の浮動小数点命令で例外が発生します
+    let x = unsafe { ptr::read_volatile(ptr::addr_of!(X)) };
+
+    if x * 2.0 == 3.0 {
+        debug::exit(debug::EXIT_SUCCESS);
+    } else {
+        debug::exit(debug::EXIT_FAILURE);
+    }
+
+    loop {}
+}
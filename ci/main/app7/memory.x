@@ -0,0 +1 @@
+../app4/memory.x
\ No newline at end of file
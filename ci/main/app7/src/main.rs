@@ -0,0 +1,17 @@
+#![no_std]
+#![no_main]
+
+use rt::entry;
+
+entry!(main, {
+    static mut BSS: u8 = 0;
+    static mut DATA: u16 = 1;
+});
+
+fn main(bss: &'static mut u8, data: &'static mut u16) -> ! {
+    // `unsafe`なしで`static mut`変数を読み書きできます
+    *bss += 1;
+    *data += 1;
+
+    loop {}
+}
@@ -0,0 +1,41 @@
+  .section .text.Reset
+  .global Reset
+  .thumb_func
+Reset:
+  ldr r0, =__rt_has_fpu
+  cmp r0, #0
+  beq 0f
+
+  ldr r0, =0xE000ED88
+  ldr r1, [r0]
+  ldr r2, =0x00F00000
+  orrs r1, r2
+  str r1, [r0]
+  dsb
+  isb
+
+0:
+  bl __pre_init
+
+  ldr r0, =_sbss
+  ldr r1, =_ebss
+  movs r2, #0
+1:
+  cmp r0, r1
+  beq 2f
+  stmia r0!, {r2}
+  b 1b
+
+2:
+  ldr r0, =_sdata
+  ldr r1, =_edata
+  ldr r2, =_sidata
+3:
+  cmp r0, r1
+  beq 4f
+  ldmia r2!, {r3}
+  stmia r0!, {r3}
+  b 3b
+
+4:
+  bl MainTrampoline
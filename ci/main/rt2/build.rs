@@ -1 +0,0 @@
-../rt/build.rs
\ No newline at end of file
@@ -1,10 +1,5 @@
-/* LM3S6965マイクロコントローラのメモリレイアウト */
-/* 1K = 1 KiBi = 1024バイト */
-MEMORY
-{
-  FLASH : ORIGIN = 0x00000000, LENGTH = 256K
-  RAM : ORIGIN = 0x20000000, LENGTH = 64K
-}
+/* デバイスのメモリレイアウト（`MEMORY`）はアプリケーションが`memory.x`で提供します */
+INCLUDE memory.x
 
 /* エントリポイントはリセットハンドラです */
 ENTRY(Reset);
@@ -31,19 +26,24 @@ SECTIONS
   .rodata :
   {
     *(.rodata .rodata.*);
+
+    /* `.data`の初期値をワード単位でコピーできるようにします */
+    . = ALIGN(4);
   } > FLASH
 
-  .bss :
+  .bss : ALIGN(4)
   {
     _sbss = .;
     *(.bss .bss.*);
+    . = ALIGN(4);
     _ebss = .;
   } > RAM
 
-  .data : AT(ADDR(.rodata) + SIZEOF(.rodata))
+  .data : AT(ADDR(.rodata) + SIZEOF(.rodata)) ALIGN(4)
   {
     _sdata = .;
     *(.data .data.*);
+    . = ALIGN(4);
     _edata = .;
   } > RAM
 
@@ -54,3 +54,23 @@ SECTIONS
     *(.ARM.exidx.*);
   }
 }
+
+/* ユーザーが`pre_init!`を使わなかった場合のデフォルト */
+PROVIDE(__pre_init = DefaultPreInit);
+
+/* ビルドスクリプトがハードフロートターゲットで1に設定します */
+PROVIDE(__rt_has_fpu = 0);
+
+/* ベクタテーブルとRAM初期化用シンボルの検査 */
+ASSERT(RESET_VECTOR == ADDR(.vector_table) + 4,
+       "ERROR(rt): the reset vector must be the second entry of the vector table");
+ASSERT(SIZEOF(.vector_table) == 8,
+       "ERROR(rt): the vector table must contain the initial SP and the reset vector");
+ASSERT(_sbss % 4 == 0 && _ebss % 4 == 0,
+       "ERROR(rt): the start and end of .bss must be 4-byte aligned");
+ASSERT(_sdata % 4 == 0 && _edata % 4 == 0,
+       "ERROR(rt): the start and end of .data must be 4-byte aligned");
+ASSERT(_sidata % 4 == 0,
+       "ERROR(rt): the load address of .data (_sidata) must be 4-byte aligned");
+ASSERT(_sidata >= ORIGIN(FLASH) && _sidata + (_edata - _sdata) <= ORIGIN(FLASH) + LENGTH(FLASH),
+       "ERROR(rt): the initial values of .data (_sidata) must be located in FLASH");
@@ -0,0 +1,42 @@
+use std::{
+    env,
+    error::Error,
+    fs,
+    path::{Path, PathBuf},
+};
+
+// `link.x`が`INCLUDE`する`memory.x`を用意します。`rt`クレートのビルドスクリプトが共有します
+//
+// リンカは`INCLUDE`するファイルを、まずカレントディレクトリ（アプリケーションクレートの
+// ルート）で、次にライブラリサーチパスで探します。そのため、アプリケーションが提供する
+// `memory.x`はビルドスクリプトが探さなくてもリンカが見つけます。アプリケーションに
+// `memory.x`がない場合は、`RT_MEMORY_X`環境変数が指すファイルをビルドディレクトリに
+// コピーします。どちらもなければ、ビルドディレクトリの`memory.x`がリンクをエラーで止めます
+pub fn provide(out_dir: &Path) -> Result<(), Box<Error>> {
+    println!("cargo:rerun-if-env-changed=RT_MEMORY_X");
+
+    let dst = out_dir.join("memory.x");
+    if let Some(path) = env::var_os("RT_MEMORY_X") {
+        let path = PathBuf::from(path);
+        if !path.is_file() {
+            return Err(format!("RT_MEMORY_X: {} is not a file", path.display()).into());
+        }
+
+        println!("cargo:rerun-if-changed={}", path.display());
+        fs::copy(&path, &dst)?;
+    } else {
+        fs::write(&dst, MISSING)?;
+    }
+
+    Ok(())
+}
+
+// `link.x`の残りの部分を処理できるよう仮の`MEMORY`を宣言し、`ASSERT`でリンクを失敗させます
+const MISSING: &str = r#"MEMORY
+{
+  FLASH : ORIGIN = 0x00000000, LENGTH = 4096K
+  RAM : ORIGIN = 0x20000000, LENGTH = 4096K
+}
+
+ASSERT(0, "ERROR(rt): memory.x not found: add it to the root of the application crate or set RT_MEMORY_X");
+"#;
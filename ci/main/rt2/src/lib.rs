@@ -1,27 +1,16 @@
 #![no_std]
 
 use core::panic::PanicInfo;
-use core::ptr;
 
-#[no_mangle]
-pub unsafe extern "C" fn Reset() -> ! {
-    // 追加!
-    // RAMの初期化
-    extern "C" {
-        static mut _sbss: u8;
-        static mut _ebss: u8;
-
-        static mut _sdata: u8;
-        static mut _edata: u8;
-        static _sidata: u8;
-    }
-
-    let count = &_ebss as *const u8 as usize - &_sbss as *const u8 as usize;
-    ptr::write_bytes(&mut _sbss as *mut u8, 0, count);
-
-    let count = &_edata as *const u8 as usize - &_sdata as *const u8 as usize;
-    ptr::copy_nonoverlapping(&_sidata as *const u8, &mut _sdata as *mut u8, count);
+// `asm.s`で実装されているリセットハンドラです。Rustのコードを実行する前に
+// `.bss`のゼロクリアと`.data`の初期化をワード単位で行います
+extern "C" {
+    fn Reset() -> !;
+}
 
+// RAMの初期化後に、アセンブリの`Reset`だけがシンボル名で呼び出します
+#[no_mangle]
+unsafe extern "C" fn MainTrampoline() -> ! {
     // ユーザーエントリポイントを呼び出します
     extern "Rust" {
         fn main() -> !;
@@ -50,5 +39,36 @@ macro_rules! entry {
 
             f()
         }
-    }
+    };
+    // `main`は一度しか呼ばれず、戻ってこないため、ここで宣言した`static mut`変数への
+    // `&'static mut`参照を安全に渡すことができます
+    ($path:path, { $(static mut $name:ident: $ty:ty = $init:expr;)* }) => {
+        #[export_name = "main"]
+        pub unsafe fn __main() -> ! {
+            $(static mut $name: $ty = $init;)*
+
+            // 与えられたパスの型チェック
+            let f: fn($(&'static mut $ty),*) -> ! = $path;
+
+            f($(&mut *::core::ptr::addr_of_mut!($name)),*)
+        }
+    };
+}
+
+// `Reset`が`bl __pre_init`で呼び出すため、C ABIを使います
+#[allow(non_snake_case)]
+#[no_mangle]
+pub extern "C" fn DefaultPreInit() {}
+
+#[macro_export]
+macro_rules! pre_init {
+    ($path:path) => {
+        #[export_name = "__pre_init"]
+        pub unsafe extern "C" fn __pre_init() {
+            // 与えられたパスの型チェック（`Reset`から呼ばれるのはこのC ABIのラッパーです）
+            let f: unsafe fn() = $path;
+
+            f()
+        }
+    };
 }
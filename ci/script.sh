@@ -85,6 +85,32 @@ main() {
         popd
     fi
 
+    pushd app5
+    cargo build
+    edition_check
+    popd
+
+    # NOTE(mps2-an386) running this requires a QEMU with Cortex-M4F support (>= 6.0)
+    pushd app6
+    cargo build
+    if qemu-system-arm -machine help | grep mps2-an386 >/dev/null; then
+        cargo run
+    else
+        echo "skipping main/app6: this QEMU doesn't support the mps2-an386 machine"
+    fi
+    edition_check
+    popd
+
+    pushd app7
+    cargo build
+    edition_check
+    popd
+
+    # check that the prebuilt `librt.a` matches `asm.s`
+    pushd rt2
+    librt_check armv6s-m
+    popd
+
     popd
 
     # # exception handling
@@ -110,6 +136,158 @@ main() {
         popd
     fi
 
+    # check that the `exception!` macro works on stable
+    pushd exceptions/app3
+    cargo build
+    # check that the prebuilt `librt.a` was assembled from the current `asm.s`
+    RT_USE_PREBUILT=1 cargo build
+    pushd ../rt
+    librt_check armv6s-m
+    popd
+    edition_check
+    popd
+
+    # check that the default handler can be overridden
+    pushd exceptions/app5
+    cargo build
+    cargo run
+    edition_check
+    popd
+
+    # check that the stack high water mark can be measured
+    pushd exceptions/app6
+    cargo build
+    cargo run
+    edition_check
+    popd
+
+    # check that .bss and .data can be placed above the stack
+    pushd exceptions/app7
+    cargo build
+    cargo run
+    edition_check
+    popd
+
+    # check that the `uninit!` macro places statics in .uninit and that they survive a reset
+    pushd exceptions/app8
+    cargo build
+    cargo run
+    edition_check
+    popd
+
+    # check that handlers can be registered at runtime
+    pushd exceptions/app10
+    cargo build
+    cargo run
+    edition_check
+    popd
+
+    # check that `ramfunc!` functions run from RAM
+    pushd exceptions/app12
+    cargo build
+    cargo run
+    edition_check
+    popd
+
+    # check that the static constructors in .init_array run before `main`
+    pushd exceptions/app13
+    cargo build
+    cargo run
+    edition_check
+    popd
+
+    # check that the boot count and the reset cause survive a software reset
+    pushd exceptions/app14
+    cargo build
+    cargo run
+    edition_check
+    popd
+
+    # check that `Reset` feeds the registered watchdog
+    pushd exceptions/app15
+    cargo build
+    cargo run
+    edition_check
+    popd
+
+    # check that the `panic-semihosting` panic handler makes QEMU exit with an error
+    pushd exceptions/app16
+    cargo build
+    if cargo run; then
+        exit 1
+    fi
+    edition_check
+    popd
+
+    # check that `main` can return a `Result`
+    pushd exceptions/app17
+    cargo build
+    cargo run
+    edition_check
+    popd
+
+    # check that returning `Err` from `main` prints the error and makes QEMU exit with failure
+    pushd exceptions/app20
+    cargo build
+    if cargo run >.stdout; then
+        exit 1
+    fi
+    grep '^Error: Overflow$' .stdout
+    rm .stdout
+    edition_check
+    popd
+
+    # check that the bootloader can start the application image
+    pushd exceptions/app11
+    cargo build
+    edition_check
+    popd
+
+    pushd exceptions/bootloader
+    cargo build
+    cargo run -- -device loader,file=../app11/target/thumbv7m-none-eabi/debug/app
+    edition_check
+    popd
+
+    # NOTE(nightly) this will require nightly until `alloc_error_handler` is stabilized
+    if [ $TRAVIS_RUST_VERSION = nightly ]; then
+        pushd exceptions/app9
+        cargo build
+        cargo run
+        edition_check
+        popd
+    fi
+
+    # NOTE(nightly) this will require nightly until `custom_test_frameworks` is stabilized
+    if [ $TRAVIS_RUST_VERSION = nightly ]; then
+        # check that the test runner reports the panicking test and runs the rest
+        pushd exceptions/app18
+        cargo build
+        if cargo test >.stdout; then
+            exit 1
+        fi
+        grep 'test result: FAILED. 3 passed; 1 failed' .stdout
+        rm .stdout
+        edition_check
+        popd
+    fi
+
+    # check that the ARMv6-M vector table builds
+    pushd exceptions/app4
+    cargo build
+    edition_check
+    popd
+
+    # check that registering a handler for an exception that ARMv6-M doesn't have is a compile error
+    pushd exceptions/app19
+    cargo check --target thumbv7m-none-eabi
+    if cargo build 2>.stderr; then
+        exit 1
+    fi
+    grep 'no variant or associated item named `MemManage`' .stderr
+    rm .stderr
+    popd
+
     # # Assembly on stable
     pushd asm
 
@@ -124,12 +302,22 @@ main() {
 
     # check that the binary blob is up to date
     pushd rt2
-    arm-none-eabi-as -march=armv7-m asm.s -o asm.o
-    ar crs librt.a asm.o
+    librt_check armv7-m
     diff librt.objdump \
          <(arm-none-eabi-objdump -Cd librt.a)
     popd
 
+    # check that the build script rejects a `librt.a` that doesn't match `asm.s`
+    pushd app2
+    RT_USE_PREBUILT=1 cargo build
+    cp ../rt/asm.s asm.s.bak
+    echo '  @ out of date' >> ../rt/asm.s
+    if RT_USE_PREBUILT=1 cargo build; then
+        exit 1
+    fi
+    mv asm.s.bak ../rt/asm.s
+    popd
+
     # check that the disassembly matches
     pushd app2
     diff release.objdump \
@@ -234,6 +422,21 @@ edition_check() {
     RUSTFLAGS="-D rust_2018_compatibility -D rust_2018_idioms" cargo check
 }
 
+# checks that the `asm.o` in the prebuilt `librt.a` is what `arm-none-eabi-as` assembles from `asm.s`
+# the code bytes and the relocations of both objects must be identical
+librt_check() {
+    local dir=$(mktemp -d)
+    mkdir $dir/fresh $dir/prebuilt
+    arm-none-eabi-as -march=$1 asm.s -o $dir/fresh/asm.o
+    cp librt.a $dir/prebuilt
+    pushd $dir/prebuilt
+    ar x librt.a asm.o
+    popd
+    diff <(cd $dir/fresh && arm-none-eabi-objdump -dr asm.o) \
+         <(cd $dir/prebuilt && arm-none-eabi-objdump -dr asm.o)
+    rm -r $dir
+}
+
 # checks that QEMU doesn't crash and that it produces no error messages
 qemu_check() {
     qemu-system-arm \